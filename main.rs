@@ -5,7 +5,11 @@ use std::io::{self, Read};
 use std::env;
 use time::{OffsetDateTime, format_description::well_known::Rfc3339};
 use anyhow::{Result, Context};
-use rusqlite::{Connection, params};
+use rusqlite::{Connection, OptionalExtension, params};
+use object_store::{ObjectStore, path::Path as ObjectPath};
+use futures::stream::StreamExt;
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use redis::Commands;
 
 use clap::Parser;
 use tracing::{info, warn, error};
@@ -34,150 +38,1664 @@ struct Args {
     /// Downloads snippet content from a given URL when creating a snippet.
     #[arg(long)]
     download: Option<String>,
+
+    /// Lists snippets, optionally filtered and paginated. Combine with
+    /// `--name-prefix`, `--created-after`, `--created-before`, `--limit`
+    /// and `--marker`.
+    #[arg(long)]
+    list: bool,
+
+    /// Restricts `--list` to snippets whose name starts with this prefix.
+    #[arg(long)]
+    name_prefix: Option<String>,
+
+    /// Restricts `--list` to snippets created at or after this RFC 3339 timestamp.
+    #[arg(long)]
+    created_after: Option<String>,
+
+    /// Restricts `--list` to snippets created at or before this RFC 3339 timestamp.
+    #[arg(long)]
+    created_before: Option<String>,
+
+    /// Caps the number of snippets returned by `--list`.
+    #[arg(long)]
+    limit: Option<usize>,
+
+    /// Resumes `--list` pagination after this snippet name (keyset pagination).
+    #[arg(long)]
+    marker: Option<String>,
+
+    /// Issues a signed bearer token instead of operating on a snippet.
+    /// Requires `--owner` and `--perms`; scope to a single snippet with
+    /// `--name`, or omit `--name` to grant access to all snippets (`*`).
+    #[arg(long)]
+    issue_token: bool,
+
+    /// Owner to issue a token for, or the owner to record when creating a
+    /// snippet with `--name`.
+    #[arg(long)]
+    owner: Option<String>,
+
+    /// Comma-separated permissions to grant a token, e.g. `read,write`.
+    #[arg(long)]
+    perms: Option<String>,
+
+    /// How long an issued token stays valid, e.g. `1h`, `30m`, `7d`.
+    #[arg(long)]
+    expires: Option<String>,
+
+    /// Bearer token authorizing `--read`, `--delete` or `--name` against an
+    /// owned snippet.
+    #[arg(long)]
+    token: Option<String>,
+
+    /// Lists metadata for all issued tokens.
+    #[arg(long)]
+    list_tokens: bool,
+
+    /// Revokes a previously issued token by id.
+    #[arg(long)]
+    revoke_token: Option<String>,
+
+    /// Runs a long-lived HTTP server over the snippet store instead of a
+    /// one-shot CLI command. Requires the `server` feature.
+    #[arg(long)]
+    serve: bool,
+
+    /// Address to bind `--serve` to. Defaults to `127.0.0.1:8080`.
+    #[arg(long)]
+    listen: Option<String>,
+
+    /// Path to a TOML or JSON config file. Falls back to
+    /// `SNIPPETS_APP_CONFIG` when omitted; environment variables still take
+    /// precedence over both.
+    #[arg(long)]
+    config: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Snippet {
+    pub content: String,
+    pub created_at: OffsetDateTime,
+    /// The snippet's owner, if any. Unowned snippets remain world-readable
+    /// and world-writable for backward compatibility with pre-auth stores.
+    #[serde(default)]
+    pub owner: Option<String>,
+}
+
+/// A permission a bearer token can grant over a snippet or set of snippets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Permission {
+    Read,
+    Write,
+    Delete,
+}
+
+impl Permission {
+    fn parse_list(raw: &str) -> Result<Vec<Permission>> {
+        raw.split(',')
+            .map(|p| match p.trim() {
+                "read" => Ok(Permission::Read),
+                "write" => Ok(Permission::Write),
+                "delete" => Ok(Permission::Delete),
+                other => anyhow::bail!("Unknown permission '{}', expected read/write/delete", other),
+            })
+            .collect()
+    }
+}
+
+/// Claims embedded in a signed bearer token, mirroring a capability: who it
+/// was issued to, what it grants, and over which snippet(s).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenClaims {
+    /// Token id, used to look up and revoke the matching `TokenRecord`.
+    pub jti: String,
+    pub owner: String,
+    pub perms: Vec<Permission>,
+    /// A snippet name, or `*` for all snippets.
+    pub scope: String,
+    /// Unix timestamp expiry, checked by `authorize` after decoding (the JWT
+    /// library's own `exp` validation is turned off).
+    pub exp: Option<i64>,
+}
+
+/// Metadata for an issued token, persisted alongside snippets so tokens can
+/// be listed and revoked without needing to hold the signed JWT itself.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenRecord {
+    pub id: String,
+    pub owner: String,
+    pub perms: Vec<Permission>,
+    pub scope: String,
+    pub expires_at: Option<OffsetDateTime>,
+    pub revoked: bool,
+}
+
+/// Criteria for `SnippetStorage::list`, supporting keyset pagination so
+/// large stores can be paged through without loading everything at once.
+#[derive(Debug, Default, Clone)]
+pub struct ListSnippetsQuery {
+    /// Only return snippets whose name starts with this prefix.
+    pub name_prefix: Option<String>,
+    /// Only return snippets created at or after this time.
+    pub created_after: Option<OffsetDateTime>,
+    /// Only return snippets created at or before this time.
+    pub created_before: Option<OffsetDateTime>,
+    /// Maximum number of snippets to return.
+    pub limit: Option<usize>,
+    /// Resume after this name (exclusive), ordered lexicographically by name.
+    pub marker: Option<String>,
+}
+
+/// Shared filter/sort/paginate logic behind `SnippetStorage::list` for
+/// backends that list by loading everything into memory first
+/// (`SqliteStorage` instead pushes this down into SQL).
+fn list_in_memory(data: HashMap<String, Snippet>, query: &ListSnippetsQuery) -> Vec<(String, Snippet)> {
+    let mut entries: Vec<(String, Snippet)> = data
+        .into_iter()
+        .filter(|(name, snippet)| {
+            if let Some(prefix) = &query.name_prefix {
+                if !name.starts_with(prefix.as_str()) {
+                    return false;
+                }
+            }
+            if let Some(after) = query.created_after {
+                if snippet.created_at < after {
+                    return false;
+                }
+            }
+            if let Some(before) = query.created_before {
+                if snippet.created_at > before {
+                    return false;
+                }
+            }
+            if let Some(marker) = &query.marker {
+                if name <= marker {
+                    return false;
+                }
+            }
+            true
+        })
+        .collect();
+
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+    if let Some(limit) = query.limit {
+        entries.truncate(limit);
+    }
+
+    entries
+}
+
+pub trait SnippetStorage {
+    fn load(&mut self) -> Result<HashMap<String, Snippet>>;
+    fn save(&mut self, data: &HashMap<String, Snippet>) -> Result<()>;
+    fn list(&mut self, query: ListSnippetsQuery) -> Result<Vec<(String, Snippet)>>;
+
+    /// Creates or overwrites a single snippet without touching the rest of the store.
+    fn create(&mut self, name: &str, snippet: &Snippet) -> Result<()>;
+    /// Fetches a single snippet by name, if it exists.
+    fn get(&mut self, name: &str) -> Result<Option<Snippet>>;
+    /// Removes a single snippet by name, returning whether it existed.
+    fn delete(&mut self, name: &str) -> Result<bool>;
+
+    /// Persists metadata for a newly issued token.
+    fn issue_token(&mut self, record: &TokenRecord) -> Result<()>;
+    /// Fetches token metadata by id, if it was ever issued.
+    fn get_token(&mut self, id: &str) -> Result<Option<TokenRecord>>;
+    /// Lists metadata for all issued tokens.
+    fn list_tokens(&mut self) -> Result<Vec<TokenRecord>>;
+    /// Marks a token as revoked by id, returning whether it existed.
+    fn revoke_token(&mut self, id: &str) -> Result<bool>;
+}
+
+pub struct JsonStorage {
+    path: String,
+}
+
+impl JsonStorage {
+    pub fn new(path: String) -> Self {
+        Self { path }
+    }
+
+    /// Tokens live in a sidecar file next to the snippet store, keeping the
+    /// existing snippet JSON format unchanged for backward compatibility.
+    fn tokens_path(&self) -> String {
+        format!("{}.tokens.json", self.path)
+    }
+
+    fn load_tokens(&self) -> Result<HashMap<String, TokenRecord>> {
+        let path = self.tokens_path();
+        if !std::path::Path::new(&path).exists() {
+            return Ok(HashMap::new());
+        }
+
+        let file = File::open(&path)
+            .with_context(|| format!("Cannot open token file '{}'", path))?;
+
+        serde_json::from_reader(file)
+            .with_context(|| format!("Cannot parse token file '{}'", path))
+    }
+
+    fn save_tokens(&self, tokens: &HashMap<String, TokenRecord>) -> Result<()> {
+        let path = self.tokens_path();
+        let file = File::create(&path)
+            .with_context(|| format!("Cannot create token file '{}'", path))?;
+
+        serde_json::to_writer_pretty(file, tokens)
+            .with_context(|| "Failed to write token file".to_string())
+    }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct Snippet {
-    pub content: String,
-    pub created_at: OffsetDateTime,
-}
+impl SnippetStorage for JsonStorage {
+    fn load(&mut self) -> Result<HashMap<String, Snippet>> {
+        if !std::path::Path::new(&self.path).exists() {
+            return Ok(HashMap::new());
+        }
+
+        let file = File::open(&self.path)
+            .with_context(|| format!("Cannot open JSON file '{}'", self.path))?;
+
+        let data = serde_json::from_reader(file)
+            .with_context(|| format!("Cannot parse JSON file '{}'", self.path))?;
+
+        Ok(data)
+    }
+
+    fn save(&mut self, data: &HashMap<String, Snippet>) -> Result<()> {
+        let file = File::create(&self.path)
+            .with_context(|| format!("Cannot create JSON file '{}'", self.path))?;
+
+        serde_json::to_writer_pretty(file, data)
+            .with_context(|| "Failed to write JSON".to_string())?;
+
+        Ok(())
+    }
+
+    fn list(&mut self, query: ListSnippetsQuery) -> Result<Vec<(String, Snippet)>> {
+        let data = self.load()?;
+        Ok(list_in_memory(data, &query))
+    }
+
+    fn create(&mut self, name: &str, snippet: &Snippet) -> Result<()> {
+        let mut data = self.load()?;
+        data.insert(name.to_string(), Snippet {
+            content: snippet.content.clone(),
+            created_at: snippet.created_at,
+            owner: snippet.owner.clone(),
+        });
+        self.save(&data)
+    }
+
+    fn get(&mut self, name: &str) -> Result<Option<Snippet>> {
+        let mut data = self.load()?;
+        Ok(data.remove(name))
+    }
+
+    fn delete(&mut self, name: &str) -> Result<bool> {
+        let mut data = self.load()?;
+        let existed = data.remove(name).is_some();
+        if existed {
+            self.save(&data)?;
+        }
+        Ok(existed)
+    }
+
+    fn issue_token(&mut self, record: &TokenRecord) -> Result<()> {
+        let mut tokens = self.load_tokens()?;
+        tokens.insert(record.id.clone(), record.clone());
+        self.save_tokens(&tokens)
+    }
+
+    fn get_token(&mut self, id: &str) -> Result<Option<TokenRecord>> {
+        let tokens = self.load_tokens()?;
+        Ok(tokens.get(id).cloned())
+    }
+
+    fn list_tokens(&mut self) -> Result<Vec<TokenRecord>> {
+        let tokens = self.load_tokens()?;
+        Ok(tokens.into_values().collect())
+    }
+
+    fn revoke_token(&mut self, id: &str) -> Result<bool> {
+        let mut tokens = self.load_tokens()?;
+        match tokens.get_mut(id) {
+            Some(record) => {
+                record.revoked = true;
+                self.save_tokens(&tokens)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
+
+pub struct SqliteStorage {
+    conn: Connection,
+}
+
+impl SqliteStorage {
+    pub fn new(path: String) -> Result<Self> {
+        let conn = Connection::open(&path)
+            .with_context(|| format!("Failed to open SQLite '{}'", path))?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS snippets (
+                name TEXT PRIMARY KEY,
+                content TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                owner TEXT
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS tokens (
+                id TEXT PRIMARY KEY,
+                owner TEXT NOT NULL,
+                perms TEXT NOT NULL,
+                scope TEXT NOT NULL,
+                expires_at TEXT,
+                revoked INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        Ok(Self { conn })
+    }
+
+    fn row_to_token(row: &rusqlite::Row) -> rusqlite::Result<TokenRecord> {
+        let id: String = row.get(0)?;
+        let owner: String = row.get(1)?;
+        let perms_json: String = row.get(2)?;
+        let scope: String = row.get(3)?;
+        let expires_at_str: Option<String> = row.get(4)?;
+        let revoked: bool = row.get(5)?;
+
+        let perms: Vec<Permission> = serde_json::from_str(&perms_json).unwrap();
+        let expires_at = expires_at_str.map(|s| OffsetDateTime::parse(&s, &Rfc3339).unwrap());
+
+        Ok(TokenRecord { id, owner, perms, scope, expires_at, revoked })
+    }
+
+    /// Escapes `%`, `_` and the escape character itself so a `name_prefix`
+    /// containing SQL wildcard characters is matched literally instead of
+    /// as a `LIKE` pattern.
+    fn escape_like_prefix(prefix: &str) -> String {
+        prefix
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_")
+    }
+}
+
+impl SnippetStorage for SqliteStorage {
+    fn load(&mut self) -> Result<HashMap<String, Snippet>> {
+        let mut stmt = self.conn.prepare("SELECT name, content, created_at, owner FROM snippets")?;
+        let rows = stmt.query_map([], |row| {
+            let name: String = row.get(0)?;
+            let content: String = row.get(1)?;
+            let created_at_str: String = row.get(2)?;
+            let owner: Option<String> = row.get(3)?;
+            let created_at = OffsetDateTime::parse(&created_at_str, &Rfc3339)
+                .unwrap();
+
+            Ok((name, Snippet { content, created_at, owner }))
+        })?;
+
+        let mut map = HashMap::new();
+        for r in rows {
+            let (name, sn) = r?;
+            map.insert(name, sn);
+        }
+
+        Ok(map)
+    }
+
+    fn save(&mut self, data: &HashMap<String, Snippet>) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        tx.execute("DELETE FROM snippets", [])?;
+
+        for (name, snippet) in data {
+            tx.execute(
+                "INSERT INTO snippets (name, content, created_at, owner) VALUES (?, ?, ?, ?)",
+                params![
+                    name,
+                    snippet.content,
+                    snippet.created_at.format(&Rfc3339)?,
+                    snippet.owner,
+                ],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn list(&mut self, query: ListSnippetsQuery) -> Result<Vec<(String, Snippet)>> {
+        let name_prefix = Self::escape_like_prefix(&query.name_prefix.unwrap_or_default());
+        let created_after = query
+            .created_after
+            .unwrap_or(OffsetDateTime::UNIX_EPOCH)
+            .format(&Rfc3339)?;
+        let created_before = query
+            .created_before
+            .unwrap_or(OffsetDateTime::new_utc(
+                time::Date::from_calendar_date(9999, time::Month::December, 31)?,
+                time::Time::MIDNIGHT,
+            ))
+            .format(&Rfc3339)?;
+        let marker = query.marker.unwrap_or_default();
+        let limit = query.limit.unwrap_or(i64::MAX as usize) as i64;
+
+        let mut stmt = self.conn.prepare(
+            "SELECT name, content, created_at, owner FROM snippets
+             WHERE name LIKE ?||'%' ESCAPE '\\' AND created_at BETWEEN ? AND ? AND name > ?
+             ORDER BY name LIMIT ?",
+        )?;
+
+        let rows = stmt.query_map(
+            params![name_prefix, created_after, created_before, marker, limit],
+            |row| {
+                let name: String = row.get(0)?;
+                let content: String = row.get(1)?;
+                let created_at_str: String = row.get(2)?;
+                let owner: Option<String> = row.get(3)?;
+                let created_at = OffsetDateTime::parse(&created_at_str, &Rfc3339)
+                    .unwrap();
+
+                Ok((name, Snippet { content, created_at, owner }))
+            },
+        )?;
+
+        let mut entries = Vec::new();
+        for r in rows {
+            entries.push(r?);
+        }
+
+        Ok(entries)
+    }
+
+    fn create(&mut self, name: &str, snippet: &Snippet) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "INSERT OR REPLACE INTO snippets (name, content, created_at, owner) VALUES (?, ?, ?, ?)",
+            params![name, snippet.content, snippet.created_at.format(&Rfc3339)?, snippet.owner],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn get(&mut self, name: &str) -> Result<Option<Snippet>> {
+        let tx = self.conn.transaction()?;
+        let result = tx
+            .query_row(
+                "SELECT content, created_at, owner FROM snippets WHERE name = ?",
+                params![name],
+                |row| {
+                    let content: String = row.get(0)?;
+                    let created_at_str: String = row.get(1)?;
+                    let owner: Option<String> = row.get(2)?;
+                    let created_at = OffsetDateTime::parse(&created_at_str, &Rfc3339)
+                        .unwrap();
+                    Ok(Snippet { content, created_at, owner })
+                },
+            )
+            .optional()?;
+        tx.commit()?;
+        Ok(result)
+    }
+
+    fn delete(&mut self, name: &str) -> Result<bool> {
+        let tx = self.conn.transaction()?;
+        let affected = tx.execute("DELETE FROM snippets WHERE name = ?", params![name])?;
+        tx.commit()?;
+        Ok(affected > 0)
+    }
+
+    fn issue_token(&mut self, record: &TokenRecord) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "INSERT OR REPLACE INTO tokens (id, owner, perms, scope, expires_at, revoked)
+             VALUES (?, ?, ?, ?, ?, ?)",
+            params![
+                record.id,
+                record.owner,
+                serde_json::to_string(&record.perms)?,
+                record.scope,
+                record.expires_at.map(|t| t.format(&Rfc3339)).transpose()?,
+                record.revoked,
+            ],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn get_token(&mut self, id: &str) -> Result<Option<TokenRecord>> {
+        let tx = self.conn.transaction()?;
+        let result = tx
+            .query_row(
+                "SELECT id, owner, perms, scope, expires_at, revoked FROM tokens WHERE id = ?",
+                params![id],
+                Self::row_to_token,
+            )
+            .optional()?;
+        tx.commit()?;
+        Ok(result)
+    }
+
+    fn list_tokens(&mut self) -> Result<Vec<TokenRecord>> {
+        let mut stmt = self.conn.prepare("SELECT id, owner, perms, scope, expires_at, revoked FROM tokens")?;
+        let rows = stmt.query_map([], Self::row_to_token)?;
+
+        let mut records = Vec::new();
+        for r in rows {
+            records.push(r?);
+        }
+
+        Ok(records)
+    }
+
+    fn revoke_token(&mut self, id: &str) -> Result<bool> {
+        let tx = self.conn.transaction()?;
+        let affected = tx.execute("UPDATE tokens SET revoked = 1 WHERE id = ?", params![id])?;
+        tx.commit()?;
+        Ok(affected > 0)
+    }
+}
+
+/// Stores snippets as one object per snippet (`{prefix}/snippets/{name}.json`,
+/// with token records under `{prefix}/tokens/`) behind
+/// a uniform object-store API, so the same code path serves S3, GCS, Azure
+/// Blob and the local filesystem depending on the URL scheme given to
+/// `ObjectStorage::new`. Credentials, endpoint and region are picked up from
+/// the standard env vars each backend already understands (e.g. `AWS_*`,
+/// `GOOGLE_*`, `AZURE_*`).
+pub struct ObjectStorage {
+    store: Box<dyn ObjectStore>,
+    prefix: ObjectPath,
+    rt: tokio::runtime::Runtime,
+}
+
+impl ObjectStorage {
+    pub fn new(url: &str) -> Result<Self> {
+        let parsed = url::Url::parse(url)
+            .with_context(|| format!("Invalid object store URL '{}'", url))?;
+
+        let (store, prefix) = object_store::parse_url(&parsed)
+            .with_context(|| format!("Cannot initialize object store for '{}'", url))?;
+
+        let rt = tokio::runtime::Runtime::new()
+            .context("Failed to start async runtime for object store")?;
+
+        Ok(Self { store, prefix, rt })
+    }
+
+    fn snippets_prefix(&self) -> ObjectPath {
+        self.prefix.child("snippets")
+    }
+
+    fn object_path(&self, name: &str) -> ObjectPath {
+        self.snippets_prefix().child(format!("{}.json", name))
+    }
+
+    fn name_from_path(&self, path: &ObjectPath) -> Option<String> {
+        path.filename()?.strip_suffix(".json").map(str::to_string)
+    }
+
+    fn tokens_prefix(&self) -> ObjectPath {
+        self.prefix.child("tokens")
+    }
+
+    fn token_path(&self, id: &str) -> ObjectPath {
+        self.tokens_prefix().child(format!("{}.json", id))
+    }
+}
+
+impl SnippetStorage for ObjectStorage {
+    fn load(&mut self) -> Result<HashMap<String, Snippet>> {
+        self.rt.block_on(async {
+            let mut map = HashMap::new();
+            let mut stream = self.store.list(Some(&self.snippets_prefix()));
+
+            while let Some(meta) = stream.next().await {
+                let meta = meta?;
+                if let Some(name) = self.name_from_path(&meta.location) {
+                    let bytes = self.store.get(&meta.location).await?.bytes().await?;
+                    let snippet: Snippet = serde_json::from_slice(&bytes)
+                        .with_context(|| format!("Cannot parse object '{}'", meta.location))?;
+                    map.insert(name, snippet);
+                }
+            }
+
+            Ok(map)
+        })
+    }
+
+    fn save(&mut self, data: &HashMap<String, Snippet>) -> Result<()> {
+        let existing = self.load()?;
+
+        for name in existing.keys() {
+            if !data.contains_key(name) {
+                self.delete(name)?;
+            }
+        }
+
+        for (name, snippet) in data {
+            self.create(name, snippet)?;
+        }
+
+        Ok(())
+    }
+
+    fn list(&mut self, query: ListSnippetsQuery) -> Result<Vec<(String, Snippet)>> {
+        let data = self.load()?;
+        Ok(list_in_memory(data, &query))
+    }
+
+    fn create(&mut self, name: &str, snippet: &Snippet) -> Result<()> {
+        let path = self.object_path(name);
+        let body = serde_json::to_vec(snippet)
+            .with_context(|| format!("Cannot serialize snippet '{}'", name))?;
+
+        self.rt.block_on(async {
+            self.store.put(&path, body.into()).await?;
+            Ok(())
+        })
+    }
+
+    fn get(&mut self, name: &str) -> Result<Option<Snippet>> {
+        let path = self.object_path(name);
+
+        self.rt.block_on(async {
+            match self.store.get(&path).await {
+                Ok(result) => {
+                    let bytes = result.bytes().await?;
+                    let snippet = serde_json::from_slice(&bytes)
+                        .with_context(|| format!("Cannot parse object '{}'", path))?;
+                    Ok(Some(snippet))
+                }
+                Err(object_store::Error::NotFound { .. }) => Ok(None),
+                Err(e) => Err(e.into()),
+            }
+        })
+    }
+
+    fn delete(&mut self, name: &str) -> Result<bool> {
+        let path = self.object_path(name);
+
+        self.rt.block_on(async {
+            match self.store.delete(&path).await {
+                Ok(()) => Ok(true),
+                Err(object_store::Error::NotFound { .. }) => Ok(false),
+                Err(e) => Err(e.into()),
+            }
+        })
+    }
+
+    fn issue_token(&mut self, record: &TokenRecord) -> Result<()> {
+        let path = self.token_path(&record.id);
+        let body = serde_json::to_vec(record)
+            .with_context(|| format!("Cannot serialize token '{}'", record.id))?;
+
+        self.rt.block_on(async {
+            self.store.put(&path, body.into()).await?;
+            Ok(())
+        })
+    }
+
+    fn get_token(&mut self, id: &str) -> Result<Option<TokenRecord>> {
+        let path = self.token_path(id);
+
+        self.rt.block_on(async {
+            match self.store.get(&path).await {
+                Ok(result) => {
+                    let bytes = result.bytes().await?;
+                    let record = serde_json::from_slice(&bytes)
+                        .with_context(|| format!("Cannot parse token object '{}'", path))?;
+                    Ok(Some(record))
+                }
+                Err(object_store::Error::NotFound { .. }) => Ok(None),
+                Err(e) => Err(e.into()),
+            }
+        })
+    }
+
+    fn list_tokens(&mut self) -> Result<Vec<TokenRecord>> {
+        self.rt.block_on(async {
+            let mut records = Vec::new();
+            let mut stream = self.store.list(Some(&self.tokens_prefix()));
+
+            while let Some(meta) = stream.next().await {
+                let meta = meta?;
+                let bytes = self.store.get(&meta.location).await?.bytes().await?;
+                let record: TokenRecord = serde_json::from_slice(&bytes)
+                    .with_context(|| format!("Cannot parse token object '{}'", meta.location))?;
+                records.push(record);
+            }
+
+            Ok(records)
+        })
+    }
+
+    fn revoke_token(&mut self, id: &str) -> Result<bool> {
+        match self.get_token(id)? {
+            Some(mut record) => {
+                record.revoked = true;
+                self.issue_token(&record)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
+
+/// Keeps snippets and tokens in a plain `HashMap` with no persistence.
+/// Intended for unit tests and throwaway runs that shouldn't touch disk.
+#[derive(Default)]
+pub struct MemoryStorage {
+    snippets: HashMap<String, Snippet>,
+    tokens: HashMap<String, TokenRecord>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SnippetStorage for MemoryStorage {
+    fn load(&mut self) -> Result<HashMap<String, Snippet>> {
+        Ok(self.snippets.clone())
+    }
+
+    fn save(&mut self, data: &HashMap<String, Snippet>) -> Result<()> {
+        self.snippets = data.clone();
+        Ok(())
+    }
+
+    fn list(&mut self, query: ListSnippetsQuery) -> Result<Vec<(String, Snippet)>> {
+        let data = self.load()?;
+        Ok(list_in_memory(data, &query))
+    }
+
+    fn create(&mut self, name: &str, snippet: &Snippet) -> Result<()> {
+        self.snippets.insert(name.to_string(), snippet.clone());
+        Ok(())
+    }
+
+    fn get(&mut self, name: &str) -> Result<Option<Snippet>> {
+        Ok(self.snippets.get(name).cloned())
+    }
+
+    fn delete(&mut self, name: &str) -> Result<bool> {
+        Ok(self.snippets.remove(name).is_some())
+    }
+
+    fn issue_token(&mut self, record: &TokenRecord) -> Result<()> {
+        self.tokens.insert(record.id.clone(), record.clone());
+        Ok(())
+    }
+
+    fn get_token(&mut self, id: &str) -> Result<Option<TokenRecord>> {
+        Ok(self.tokens.get(id).cloned())
+    }
+
+    fn list_tokens(&mut self) -> Result<Vec<TokenRecord>> {
+        Ok(self.tokens.values().cloned().collect())
+    }
+
+    fn revoke_token(&mut self, id: &str) -> Result<bool> {
+        match self.tokens.get_mut(id) {
+            Some(record) => {
+                record.revoked = true;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
+
+/// Stores each snippet as a Redis hash at `snippet:{name}`, and each issued
+/// token as a hash at `token:{id}`, so multiple machines can share one
+/// snippet store. An optional TTL from `SNIPPETS_APP_REDIS_TTL` (seconds)
+/// expires snippets automatically after they're written.
+pub struct RedisStorage {
+    client: redis::Client,
+    ttl_seconds: Option<u64>,
+}
+
+impl RedisStorage {
+    pub fn new(url: &str) -> Result<Self> {
+        let client = redis::Client::open(url)
+            .with_context(|| format!("Failed to open Redis client for '{}'", url))?;
+
+        let ttl_seconds = settings::get("redis_ttl")
+            .map(|v| v.parse::<u64>())
+            .transpose()
+            .context("SNIPPETS_APP_REDIS_TTL must be an integer number of seconds")?;
+
+        Ok(Self { client, ttl_seconds })
+    }
+
+    fn conn(&self) -> Result<redis::Connection> {
+        self.client
+            .get_connection()
+            .context("Failed to connect to Redis")
+    }
+
+    fn snippet_key(name: &str) -> String {
+        format!("snippet:{}", name)
+    }
+
+    fn token_key(id: &str) -> String {
+        format!("token:{}", id)
+    }
+}
+
+impl SnippetStorage for RedisStorage {
+    fn load(&mut self) -> Result<HashMap<String, Snippet>> {
+        let mut conn = self.conn()?;
+        let keys: Vec<String> = conn.scan_match("snippet:*")?.collect();
+
+        let mut map = HashMap::new();
+        for key in keys {
+            let name = key.strip_prefix("snippet:").unwrap().to_string();
+            let content: String = conn.hget(&key, "content")?;
+            let created_at_str: String = conn.hget(&key, "created_at")?;
+            let owner: Option<String> = conn.hget(&key, "owner")?;
+            let created_at = OffsetDateTime::parse(&created_at_str, &Rfc3339)?;
+
+            map.insert(name, Snippet { content, created_at, owner });
+        }
+
+        Ok(map)
+    }
+
+    fn save(&mut self, data: &HashMap<String, Snippet>) -> Result<()> {
+        let existing = self.load()?;
+
+        for name in existing.keys() {
+            if !data.contains_key(name) {
+                self.delete(name)?;
+            }
+        }
+
+        for (name, snippet) in data {
+            self.create(name, snippet)?;
+        }
+
+        Ok(())
+    }
+
+    fn list(&mut self, query: ListSnippetsQuery) -> Result<Vec<(String, Snippet)>> {
+        let data = self.load()?;
+        Ok(list_in_memory(data, &query))
+    }
+
+    fn create(&mut self, name: &str, snippet: &Snippet) -> Result<()> {
+        let mut conn = self.conn()?;
+        let key = Self::snippet_key(name);
+
+        conn.hset_multiple::<_, _, _, ()>(
+            &key,
+            &[
+                ("content", snippet.content.clone()),
+                ("created_at", snippet.created_at.format(&Rfc3339)?),
+            ],
+        )?;
+
+        match &snippet.owner {
+            Some(owner) => conn.hset::<_, _, _, ()>(&key, "owner", owner)?,
+            None => conn.hdel::<_, _, ()>(&key, "owner").unwrap_or(()),
+        };
+
+        if let Some(ttl) = self.ttl_seconds {
+            conn.expire::<_, ()>(&key, ttl as i64)?;
+        }
+
+        Ok(())
+    }
+
+    fn get(&mut self, name: &str) -> Result<Option<Snippet>> {
+        let mut conn = self.conn()?;
+        let key = Self::snippet_key(name);
+
+        if !conn.exists(&key)? {
+            return Ok(None);
+        }
+
+        let content: String = conn.hget(&key, "content")?;
+        let created_at_str: String = conn.hget(&key, "created_at")?;
+        let owner: Option<String> = conn.hget(&key, "owner")?;
+        let created_at = OffsetDateTime::parse(&created_at_str, &Rfc3339)?;
+
+        Ok(Some(Snippet { content, created_at, owner }))
+    }
+
+    fn delete(&mut self, name: &str) -> Result<bool> {
+        let mut conn = self.conn()?;
+        let affected: u64 = conn.del(Self::snippet_key(name))?;
+        Ok(affected > 0)
+    }
+
+    fn issue_token(&mut self, record: &TokenRecord) -> Result<()> {
+        let mut conn = self.conn()?;
+        let key = Self::token_key(&record.id);
+
+        conn.hset_multiple::<_, _, _, ()>(
+            &key,
+            &[
+                ("owner", record.owner.clone()),
+                ("perms", serde_json::to_string(&record.perms)?),
+                ("scope", record.scope.clone()),
+                ("revoked", (record.revoked as u8).to_string()),
+            ],
+        )?;
+
+        match record.expires_at {
+            Some(t) => conn.hset::<_, _, _, ()>(&key, "expires_at", t.format(&Rfc3339)?)?,
+            None => conn.hdel::<_, _, ()>(&key, "expires_at").unwrap_or(()),
+        };
+
+        Ok(())
+    }
+
+    fn get_token(&mut self, id: &str) -> Result<Option<TokenRecord>> {
+        let mut conn = self.conn()?;
+        let key = Self::token_key(id);
+
+        if !conn.exists(&key)? {
+            return Ok(None);
+        }
+
+        Ok(Some(Self::token_from_hash(&mut conn, id, &key)?))
+    }
+
+    fn list_tokens(&mut self) -> Result<Vec<TokenRecord>> {
+        let mut conn = self.conn()?;
+        let keys: Vec<String> = conn.scan_match("token:*")?.collect();
+
+        let mut records = Vec::new();
+        for key in keys {
+            let id = key.strip_prefix("token:").unwrap().to_string();
+            records.push(Self::token_from_hash(&mut conn, &id, &key)?);
+        }
+
+        Ok(records)
+    }
+
+    fn revoke_token(&mut self, id: &str) -> Result<bool> {
+        let mut conn = self.conn()?;
+        let key = Self::token_key(id);
+
+        if !conn.exists(&key)? {
+            return Ok(false);
+        }
+
+        conn.hset::<_, _, _, ()>(&key, "revoked", 1)?;
+        Ok(true)
+    }
+}
+
+impl RedisStorage {
+    fn token_from_hash(conn: &mut redis::Connection, id: &str, key: &str) -> Result<TokenRecord> {
+        let owner: String = conn.hget(key, "owner")?;
+        let perms_json: String = conn.hget(key, "perms")?;
+        let scope: String = conn.hget(key, "scope")?;
+        let expires_at_str: Option<String> = conn.hget(key, "expires_at")?;
+        let revoked_flag: i64 = conn.hget(key, "revoked")?;
+        let revoked = revoked_flag != 0;
+
+        let perms: Vec<Permission> = serde_json::from_str(&perms_json)?;
+        let expires_at = expires_at_str
+            .map(|s| OffsetDateTime::parse(&s, &Rfc3339))
+            .transpose()?;
+
+        Ok(TokenRecord { id: id.to_string(), owner, perms, scope, expires_at, revoked })
+    }
+}
+
+/// Scope granting access to every snippet, used when a token is issued
+/// without `--name`.
+const TOKEN_SCOPE_ALL: &str = "*";
+
+fn token_secret() -> Result<String> {
+    settings::get("secret").context("SNIPPETS_APP_SECRET must be set to issue or verify tokens")
+}
+
+/// Signs a new bearer token for `owner`, grants it `perms` over `scope`
+/// (a snippet name or `*`), and records its metadata in `storage` so it can
+/// later be listed or revoked.
+fn issue_token(
+    storage: &mut dyn SnippetStorage,
+    owner: String,
+    perms: Vec<Permission>,
+    scope: String,
+    expires_in: Option<std::time::Duration>,
+) -> Result<String> {
+    let secret = token_secret()?;
+    let id = uuid::Uuid::new_v4().to_string();
+
+    let expires_at = expires_in
+        .map(time::Duration::try_from)
+        .transpose()
+        .context("Token expiry is out of range")?
+        .map(|d| OffsetDateTime::now_utc() + d);
+
+    let claims = TokenClaims {
+        jti: id.clone(),
+        owner: owner.clone(),
+        perms: perms.clone(),
+        scope: scope.clone(),
+        exp: expires_at.map(|t| t.unix_timestamp()),
+    };
+
+    let token = jsonwebtoken::encode(
+        &Header::new(Algorithm::HS256),
+        &claims,
+        &EncodingKey::from_secret(secret.as_bytes()),
+    )
+    .context("Failed to sign token")?;
+
+    storage.issue_token(&TokenRecord {
+        id,
+        owner,
+        perms,
+        scope,
+        expires_at,
+        revoked: false,
+    })?;
+
+    Ok(token)
+}
+
+/// Verifies that `token` is signed, unexpired, unrevoked, and grants `perm`
+/// over `snippet_name`.
+fn authorize(
+    storage: &mut dyn SnippetStorage,
+    token: &str,
+    snippet_name: &str,
+    perm: Permission,
+) -> Result<()> {
+    let secret = token_secret()?;
+
+    // Expiry is a plain unix timestamp we check ourselves below, rather than
+    // the claims' built-in `exp` handling, since `exp` is optional here.
+    let mut validation = Validation::new(Algorithm::HS256);
+    validation.validate_exp = false;
+    validation.required_spec_claims.clear();
+
+    let data = jsonwebtoken::decode::<TokenClaims>(
+        token,
+        &DecodingKey::from_secret(secret.as_bytes()),
+        &validation,
+    )
+    .context("Token signature is invalid")?;
+
+    let claims = data.claims;
+
+    if let Some(exp) = claims.exp {
+        if OffsetDateTime::now_utc().unix_timestamp() >= exp {
+            anyhow::bail!("Token has expired");
+        }
+    }
+
+    let record = storage
+        .get_token(&claims.jti)?
+        .context("Token has been revoked")?;
+
+    if record.revoked {
+        anyhow::bail!("Token has been revoked");
+    }
+
+    if claims.scope != TOKEN_SCOPE_ALL && claims.scope != snippet_name {
+        anyhow::bail!("Token does not grant access to snippet '{}'", snippet_name);
+    }
+
+    if !claims.perms.contains(&perm) {
+        anyhow::bail!("Token does not grant '{:?}' permission", perm);
+    }
+
+    Ok(())
+}
+
+/// Requires a valid `token` whenever `name` already refers to an owned
+/// snippet; unowned (or not-yet-existing) snippets remain world-accessible.
+fn authorize_if_owned(
+    storage: &mut dyn SnippetStorage,
+    name: &str,
+    perm: Permission,
+    token: Option<&str>,
+) -> Result<()> {
+    let Some(existing) = storage.get(name)? else {
+        return Ok(());
+    };
+
+    if existing.owner.is_none() {
+        return Ok(());
+    }
+
+    let token = token.context("This snippet is owned; pass --token to access it")?;
+    authorize(storage, token, name, perm)
+}
+
+/// Filters `--list`/`GET /snippets` results down to what `token` may read:
+/// unowned snippets stay visible to everyone, while owned snippets are
+/// dropped unless `token` grants read access to that name. This mirrors
+/// `authorize_if_owned`, but works off already-fetched entries instead of
+/// re-fetching each snippet by name.
+fn filter_listable(
+    storage: &mut dyn SnippetStorage,
+    entries: Vec<(String, Snippet)>,
+    token: Option<&str>,
+) -> Vec<(String, Snippet)> {
+    entries
+        .into_iter()
+        .filter(|(name, snippet)| {
+            if snippet.owner.is_none() {
+                return true;
+            }
+            match token {
+                Some(t) => authorize(storage, t, name, Permission::Read).is_ok(),
+                None => false,
+            }
+        })
+        .collect()
+}
+
+/// Long-running HTTP server mode exposing the snippet store over REST,
+/// gated behind the `server` feature so CLI-only users don't pull in the
+/// async HTTP stack. Keeps one `SnippetStorage` alive for the process
+/// lifetime instead of opening it per request.
+#[cfg(feature = "server")]
+mod server {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use axum::{
+        extract::{Path as UrlPath, Query, State},
+        http::{HeaderMap, StatusCode},
+        response::{IntoResponse, Response},
+        routing::{get, post},
+        Json, Router,
+    };
+
+    /// Pulls a bearer token out of `Authorization: Bearer <token>`, the same
+    /// token the CLI accepts via `--token`.
+    fn bearer_token(headers: &HeaderMap) -> Option<String> {
+        headers
+            .get(axum::http::header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .map(str::to_string)
+    }
+
+    type SharedStorage = Arc<Mutex<Box<dyn SnippetStorage + Send>>>;
+
+    #[derive(Deserialize)]
+    struct CreateSnippetRequest {
+        content: String,
+        #[serde(default)]
+        owner: Option<String>,
+    }
+
+    #[derive(Serialize)]
+    struct CreateSnippetResponse {
+        name: String,
+    }
+
+    #[derive(Serialize)]
+    struct SnippetResponse {
+        content: String,
+        #[serde(with = "time::serde::rfc3339")]
+        created_at: OffsetDateTime,
+        owner: Option<String>,
+    }
+
+    impl From<Snippet> for SnippetResponse {
+        fn from(sn: Snippet) -> Self {
+            Self { content: sn.content, created_at: sn.created_at, owner: sn.owner }
+        }
+    }
+
+    #[derive(Deserialize, Default)]
+    struct ListParams {
+        prefix: Option<String>,
+        limit: Option<usize>,
+        marker: Option<String>,
+    }
 
-pub trait SnippetStorage {
-    fn load(&mut self) -> Result<HashMap<String, Snippet>>;
-    fn save(&mut self, data: &HashMap<String, Snippet>) -> Result<()>;
-}
+    #[derive(Serialize)]
+    struct ListEntry {
+        name: String,
+        #[serde(with = "time::serde::rfc3339")]
+        created_at: OffsetDateTime,
+    }
 
-pub struct JsonStorage {
-    path: String,
-}
+    #[derive(Serialize)]
+    struct ErrorResponse {
+        error: String,
+    }
 
-impl JsonStorage {
-    pub fn new(path: String) -> Self {
-        Self { path }
+    fn error_response(status: StatusCode, message: impl ToString) -> Response {
+        (status, Json(ErrorResponse { error: message.to_string() })).into_response()
     }
-}
 
-impl SnippetStorage for JsonStorage {
-    fn load(&mut self) -> Result<HashMap<String, Snippet>> {
-        if !std::path::Path::new(&self.path).exists() {
-            return Ok(HashMap::new());
-        }
+    /// Either an authorization failure (maps to 403) or a storage failure
+    /// (maps to 400), so handlers can report the right status code after
+    /// running a combined check-then-act closure.
+    enum HandlerError {
+        Forbidden(anyhow::Error),
+        Storage(anyhow::Error),
+    }
 
-        let file = File::open(&self.path)
-            .with_context(|| format!("Cannot open JSON file '{}'", self.path))?;
+    impl From<HandlerError> for Response {
+        fn from(e: HandlerError) -> Self {
+            match e {
+                HandlerError::Forbidden(e) => error_response(StatusCode::FORBIDDEN, e),
+                HandlerError::Storage(e) => error_response(StatusCode::BAD_REQUEST, e),
+            }
+        }
+    }
 
-        let data = serde_json::from_reader(file)
-            .with_context(|| format!("Cannot parse JSON file '{}'", self.path))?;
+    /// Runs a storage operation on a blocking thread instead of the async
+    /// executor. `SnippetStorage` methods are synchronous, and `ObjectStorage`
+    /// drives its own `tokio::runtime::Runtime::block_on` internally; calling
+    /// that directly from a task already running inside the server's runtime
+    /// is a nested `block_on`, which Tokio panics on.
+    async fn with_storage<T, F>(state: &SharedStorage, f: F) -> std::result::Result<T, HandlerError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut dyn SnippetStorage) -> std::result::Result<T, HandlerError> + Send + 'static,
+    {
+        let state = state.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut storage = state.lock().unwrap();
+            f(storage.as_mut())
+        })
+        .await
+        .expect("storage task panicked")
+    }
 
-        Ok(data)
+    pub fn run(storage: Box<dyn SnippetStorage + Send>, listen: String) -> Result<()> {
+        let rt = tokio::runtime::Runtime::new()
+            .context("Failed to start async runtime for the server")?;
+        rt.block_on(serve(storage, listen))
     }
 
-    fn save(&mut self, data: &HashMap<String, Snippet>) -> Result<()> {
-        let file = File::create(&self.path)
-            .with_context(|| format!("Cannot create JSON file '{}'", self.path))?;
+    async fn serve(storage: Box<dyn SnippetStorage + Send>, listen: String) -> Result<()> {
+        let state: SharedStorage = Arc::new(Mutex::new(storage));
 
-        serde_json::to_writer_pretty(file, data)
-            .with_context(|| "Failed to write JSON".to_string())?;
+        let app = Router::new()
+            .route("/snippets", post(create_snippet).get(list_snippets))
+            .route("/snippets/:name", get(get_snippet).delete(delete_snippet))
+            .with_state(state);
+
+        let listener = tokio::net::TcpListener::bind(&listen)
+            .await
+            .with_context(|| format!("Cannot bind to '{}'", listen))?;
+
+        info!("Serving snippets on {listen}");
+        axum::serve(listener, app)
+            .await
+            .context("Server exited unexpectedly")?;
 
         Ok(())
     }
-}
 
-pub struct SqliteStorage {
-    conn: Connection,
-}
+    async fn create_snippet(
+        State(state): State<SharedStorage>,
+        headers: HeaderMap,
+        Json(req): Json<CreateSnippetRequest>,
+    ) -> Response {
+        let name = uuid::Uuid::new_v4().to_string();
+        let token = bearer_token(&headers);
 
-impl SqliteStorage {
-    pub fn new(path: String) -> Result<Self> {
-        let conn = Connection::open(&path)
-            .with_context(|| format!("Failed to open SQLite '{}'", path))?;
+        let result = with_storage(&state, move |storage| {
+            authorize_if_owned(storage, &name, Permission::Write, token.as_deref())
+                .map_err(HandlerError::Forbidden)?;
 
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS snippets (
-                name TEXT PRIMARY KEY,
-                content TEXT NOT NULL,
-                created_at TEXT NOT NULL
-            )",
-            [],
-        )?;
+            let snippet = Snippet {
+                content: req.content,
+                created_at: OffsetDateTime::now_utc(),
+                owner: req.owner,
+            };
+            storage.create(&name, &snippet).map_err(HandlerError::Storage)?;
+            Ok(name)
+        })
+        .await;
 
-        Ok(Self { conn })
+        match result {
+            Ok(name) => (StatusCode::OK, Json(CreateSnippetResponse { name })).into_response(),
+            Err(e) => e.into(),
+        }
+    }
+
+    async fn get_snippet(
+        State(state): State<SharedStorage>,
+        headers: HeaderMap,
+        UrlPath(name): UrlPath<String>,
+    ) -> Response {
+        let token = bearer_token(&headers);
+
+        let result = with_storage(&state, move |storage| {
+            authorize_if_owned(storage, &name, Permission::Read, token.as_deref())
+                .map_err(HandlerError::Forbidden)?;
+            storage.get(&name).map_err(HandlerError::Storage)
+        })
+        .await;
+
+        match result {
+            Ok(Some(sn)) => Json(SnippetResponse::from(sn)).into_response(),
+            Ok(None) => error_response(StatusCode::NOT_FOUND, "Snippet not found"),
+            Err(e) => e.into(),
+        }
+    }
+
+    async fn delete_snippet(
+        State(state): State<SharedStorage>,
+        headers: HeaderMap,
+        UrlPath(name): UrlPath<String>,
+    ) -> Response {
+        let token = bearer_token(&headers);
+
+        let result = with_storage(&state, move |storage| {
+            authorize_if_owned(storage, &name, Permission::Delete, token.as_deref())
+                .map_err(HandlerError::Forbidden)?;
+            storage.delete(&name).map_err(HandlerError::Storage)
+        })
+        .await;
+
+        match result {
+            Ok(true) => StatusCode::NO_CONTENT.into_response(),
+            Ok(false) => error_response(StatusCode::NOT_FOUND, "Snippet not found"),
+            Err(e) => e.into(),
+        }
+    }
+
+    async fn list_snippets(
+        State(state): State<SharedStorage>,
+        headers: HeaderMap,
+        Query(params): Query<ListParams>,
+    ) -> Response {
+        let token = bearer_token(&headers);
+        let query = ListSnippetsQuery {
+            name_prefix: params.prefix,
+            created_after: None,
+            created_before: None,
+            limit: params.limit,
+            marker: params.marker,
+        };
+
+        let result = with_storage(&state, move |storage| {
+            let entries = storage.list(query).map_err(HandlerError::Storage)?;
+            Ok(filter_listable(storage, entries, token.as_deref()))
+        })
+        .await;
+
+        match result {
+            Ok(entries) => Json(
+                entries
+                    .into_iter()
+                    .map(|(name, sn)| ListEntry { name, created_at: sn.created_at })
+                    .collect::<Vec<_>>(),
+            )
+            .into_response(),
+            Err(e) => e.into(),
+        }
     }
 }
 
-impl SnippetStorage for SqliteStorage {
-    fn load(&mut self) -> Result<HashMap<String, Snippet>> {
-        let mut stmt = self.conn.prepare("SELECT name, content, created_at FROM snippets")?;
-        let rows = stmt.query_map([], |row| {
-            let name: String = row.get(0)?;
-            let content: String = row.get(1)?;
-            let created_at_str: String = row.get(2)?;
-            let created_at = OffsetDateTime::parse(&created_at_str, &Rfc3339)
-                .unwrap();
+/// Typed configuration, loaded once at startup so the rest of the crate
+/// stops reaching for `env::var` directly. Layering, lowest to highest
+/// precedence: built-in defaults, a TOML/JSON config file, then environment
+/// variables.
+mod settings {
+    use super::*;
+    use std::sync::RwLock;
 
-            Ok((name, Snippet { content, created_at }))
-        })?;
+    static SETTINGS: RwLock<Option<Settings>> = RwLock::new(None);
 
-        let mut map = HashMap::new();
-        for r in rows {
-            let (name, sn) = r?;
-            map.insert(name, sn);
+    #[derive(Debug, Clone)]
+    struct Settings {
+        log_level: String,
+        log_path: String,
+        storage: Option<String>,
+        listen: Option<String>,
+        extra: HashMap<String, String>,
+    }
+
+    impl Default for Settings {
+        fn default() -> Self {
+            Self {
+                log_level: "info".to_string(),
+                log_path: "snippets.log".to_string(),
+                storage: None,
+                listen: None,
+                extra: HashMap::new(),
+            }
         }
+    }
 
-        Ok(map)
+    /// Mirrors `Settings`, but every field is optional so a config file only
+    /// needs to set what it wants to override.
+    #[derive(Debug, Default, Deserialize)]
+    struct FileSettings {
+        log_level: Option<String>,
+        log_path: Option<String>,
+        storage: Option<String>,
+        listen: Option<String>,
+        #[serde(flatten)]
+        extra: HashMap<String, String>,
     }
 
-    fn save(&mut self, data: &HashMap<String, Snippet>) -> Result<()> {
-        self.conn.execute("DELETE FROM snippets", [])?;
+    fn load_file(path: &str) -> Result<FileSettings> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Cannot read config file '{}'", path))?;
 
-        for (name, snippet) in data {
-            self.conn.execute(
-                "INSERT INTO snippets (name, content, created_at) VALUES (?, ?, ?)",
-                params![
-                    name,
-                    snippet.content,
-                    snippet.created_at.format(&Rfc3339)?
-                ],
-            )?;
+        if path.ends_with(".json") {
+            serde_json::from_str(&contents)
+                .with_context(|| format!("Cannot parse JSON config file '{}'", path))
+        } else {
+            toml::from_str(&contents)
+                .with_context(|| format!("Cannot parse TOML config file '{}'", path))
+        }
+    }
+
+    /// Loads settings from `config_path` (falling back to
+    /// `SNIPPETS_APP_CONFIG`) with environment overrides layered on top, and
+    /// installs them as the process-global settings, replacing whatever was
+    /// installed before. Call once at startup; tests that need a clean slate
+    /// can call `reset_for_test` first.
+    pub fn init(config_path: Option<&str>) -> Result<()> {
+        let mut settings = Settings::default();
+
+        let path = config_path
+            .map(str::to_string)
+            .or_else(|| env::var("SNIPPETS_APP_CONFIG").ok());
+
+        if let Some(path) = path {
+            let file = load_file(&path)?;
+            if let Some(v) = file.log_level {
+                settings.log_level = v;
+            }
+            if let Some(v) = file.log_path {
+                settings.log_path = v;
+            }
+            if let Some(v) = file.storage {
+                settings.storage = Some(v);
+            }
+            if let Some(v) = file.listen {
+                settings.listen = Some(v);
+            }
+            settings.extra.extend(file.extra);
+        }
+
+        if let Ok(v) = env::var("SNIPPETS_APP_LOG_LEVEL") {
+            settings.log_level = v;
+        }
+        if let Ok(v) = env::var("SNIPPETS_APP_LOG_PATH") {
+            settings.log_path = v;
+        }
+        if let Ok(v) = env::var("SNIPPETS_APP_STORAGE") {
+            settings.storage = Some(v);
+        }
+        if let Ok(v) = env::var("SNIPPETS_APP_LISTEN") {
+            settings.listen = Some(v);
         }
 
+        *SETTINGS.write().unwrap() = Some(settings);
         Ok(())
     }
+
+    /// Drops the process-global settings so the next `init` or typed getter
+    /// starts from a clean `Settings::default()` again. Test-only: outside
+    /// tests, `init` alone is the right way to (re)configure the process.
+    #[cfg(test)]
+    pub fn reset_for_test() {
+        *SETTINGS.write().unwrap() = None;
+    }
+
+    fn current() -> Settings {
+        SETTINGS.read().unwrap().clone().unwrap_or_default()
+    }
+
+    pub fn log_level() -> String {
+        current().log_level
+    }
+
+    pub fn log_path() -> String {
+        current().log_path
+    }
+
+    pub fn storage() -> Result<String> {
+        current().storage.context(
+            "No storage configured; set SNIPPETS_APP_STORAGE or `storage` in a config file",
+        )
+    }
+
+    pub fn listen() -> Option<String> {
+        current().listen
+    }
+
+    /// Generic fallback for keys without a typed getter, plus the known
+    /// keys (`log_level`, `log_path`, `storage`, `listen`) for convenience.
+    /// Keys without a typed field check a live `SNIPPETS_APP_<KEY>`
+    /// environment variable first, then fall back to `extra` (populated
+    /// from a config file at `init` time) — the same env-over-file
+    /// precedence the typed fields get from `init` layering env after the
+    /// file, so a config-file value can't permanently shadow its env var.
+    pub fn get(key: &str) -> Option<String> {
+        let settings = current();
+        match key {
+            "log_level" => Some(settings.log_level.clone()),
+            "log_path" => Some(settings.log_path.clone()),
+            "storage" => settings.storage.clone(),
+            "listen" => settings.listen.clone(),
+            _ => env::var(format!("SNIPPETS_APP_{}", key.to_uppercase()))
+                .ok()
+                .or_else(|| settings.extra.get(key).cloned()),
+        }
+    }
 }
 
-fn init_logging() {
-    let level = env::var("SNIPPETS_APP_LOG_LEVEL").unwrap_or("info".into());
-    let log_path = env::var("SNIPPETS_APP_LOG_PATH").unwrap_or("snippets.log".into());
+fn init_logging() -> Result<()> {
+    let log_path = settings::log_path();
+    let log_file = std::fs::File::create(&log_path)
+        .with_context(|| format!("Cannot create log file '{}'", log_path))?;
 
     tracing_subscriber::fmt()
-        .with_env_filter(EnvFilter::new(level))
-        .with_writer(std::fs::File::create(log_path).unwrap())
+        .with_env_filter(EnvFilter::new(settings::log_level()))
+        .with_writer(log_file)
         .init();
+
+    Ok(())
 }
 
-fn init_storage() -> Result<Box<dyn SnippetStorage>> {
-    let config = env::var("SNIPPETS_APP_STORAGE")
-        .context("Environment variable SNIPPETS_APP_STORAGE is not set")?;
+fn init_storage() -> Result<Box<dyn SnippetStorage + Send>> {
+    let config = settings::storage()?;
 
     let (kind, path) = config
         .split_once(':')
-        .context("SNIPPETS_APP_STORAGE must be JSON:path or SQLITE:path")?;
+        .context("storage must be JSON:path, SQLITE:path, OBJECT:url, MEMORY: or REDIS:url")?;
 
     match kind {
         "JSON" => Ok(Box::new(JsonStorage::new(path.into()))),
         "SQLITE" => Ok(Box::new(SqliteStorage::new(path.into())?)),
+        "OBJECT" => Ok(Box::new(ObjectStorage::new(path)?)),
+        "MEMORY" => Ok(Box::new(MemoryStorage::new())),
+        "REDIS" => Ok(Box::new(RedisStorage::new(path)?)),
         _ => anyhow::bail!("Unknown storage provider"),
     }
 }
 
 fn main() -> Result<()> {
-    init_logging();
     let args = Args::parse();
+    settings::init(args.config.as_deref())?;
+    init_logging()?;
 
     let mut storage = init_storage()?;
-    let mut map = storage.load()?;
+
+    if args.serve {
+        let listen = args
+            .listen
+            .or_else(settings::listen)
+            .unwrap_or_else(|| "127.0.0.1:8080".to_string());
+
+        #[cfg(feature = "server")]
+        {
+            return server::run(storage, listen);
+        }
+
+        #[cfg(not(feature = "server"))]
+        {
+            let _ = listen;
+            anyhow::bail!(
+                "This binary was built without the `server` feature; rebuild with `--features server` to use --serve"
+            );
+        }
+    }
+
+    if args.issue_token {
+        let owner = args.owner.context("--issue-token requires --owner")?;
+        let perms = Permission::parse_list(
+            &args.perms.context("--issue-token requires --perms")?,
+        )?;
+        let scope = args.name.clone().unwrap_or_else(|| TOKEN_SCOPE_ALL.to_string());
+        let expires_in = args
+            .expires
+            .map(|s| humantime::parse_duration(&s))
+            .transpose()
+            .context("Invalid --expires duration, expected e.g. '1h', '30m', '7d'")?;
+
+        let token = issue_token(storage.as_mut(), owner, perms, scope, expires_in)?;
+        println!("{}", token);
+        return Ok(());
+    }
+
+    if args.list_tokens {
+        for record in storage.list_tokens()? {
+            println!(
+                "{}\towner={}\tscope={}\tperms={:?}\trevoked={}",
+                record.id, record.owner, record.scope, record.perms, record.revoked
+            );
+        }
+        return Ok(());
+    }
+
+    if let Some(id) = args.revoke_token {
+        if storage.revoke_token(&id)? {
+            println!("Token revoked.");
+        } else {
+            println!("Token not found.");
+        }
+        return Ok(());
+    }
 
     if let Some(name) = args.name {
+        authorize_if_owned(storage.as_mut(), &name, Permission::Write, args.token.as_deref())?;
+
         let content = if let Some(url) = args.download {
             info!("Downloading snippet from {url}");
             reqwest::blocking::get(url)?.text()?
@@ -190,17 +1708,19 @@ fn main() -> Result<()> {
         let sn = Snippet {
             content,
             created_at: OffsetDateTime::now_utc(),
+            owner: args.owner,
         };
 
-        map.insert(name, sn);
-        storage.save(&map)?;
+        storage.create(&name, &sn)?;
         info!("Snippet saved");
         println!("Snippet saved.");
         return Ok(());
     }
 
     if let Some(name) = args.read {
-        if let Some(sn) = map.get(&name) {
+        authorize_if_owned(storage.as_mut(), &name, Permission::Read, args.token.as_deref())?;
+
+        if let Some(sn) = storage.get(&name)? {
             println!("Created at: {}", sn.created_at.format(&Rfc3339)?);
             println!("{}", sn.content);
         } else {
@@ -209,9 +1729,35 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    if args.list {
+        let query = ListSnippetsQuery {
+            name_prefix: args.name_prefix,
+            created_after: args
+                .created_after
+                .map(|s| OffsetDateTime::parse(&s, &Rfc3339))
+                .transpose()
+                .context("Invalid --created-after timestamp, expected RFC 3339")?,
+            created_before: args
+                .created_before
+                .map(|s| OffsetDateTime::parse(&s, &Rfc3339))
+                .transpose()
+                .context("Invalid --created-before timestamp, expected RFC 3339")?,
+            limit: args.limit,
+            marker: args.marker,
+        };
+
+        let entries = storage.list(query)?;
+        let entries = filter_listable(storage.as_mut(), entries, args.token.as_deref());
+        for (name, sn) in entries {
+            println!("{}\t{}", name, sn.created_at.format(&Rfc3339)?);
+        }
+        return Ok(());
+    }
+
     if let Some(name) = args.delete {
-        if map.remove(&name).is_some() {
-            storage.save(&map)?;
+        authorize_if_owned(storage.as_mut(), &name, Permission::Delete, args.token.as_deref())?;
+
+        if storage.delete(&name)? {
             println!("Snippet deleted.");
         } else {
             println!("Snippet not found.");
@@ -220,9 +1766,13 @@ fn main() -> Result<()> {
     }
 
     println!("Usage:");
-    println!("  --name <name> [--download URL]");
-    println!("  --read <name>");
-    println!("  --delete <name>");
+    println!("  --name <name> [--download URL] [--owner OWNER] [--token TOKEN]");
+    println!("  --read <name> [--token TOKEN]");
+    println!("  --delete <name> [--token TOKEN]");
+    println!("  --list [--name-prefix P] [--created-after T] [--created-before T] [--limit N] [--marker NAME]");
+    println!("  --issue-token --owner OWNER --perms read,write,delete [--name SCOPE] [--expires 1h]");
+    println!("  --list-tokens");
+    println!("  --revoke-token <id>");
 
     Ok(())
 }
@@ -231,13 +1781,24 @@ fn main() -> Result<()> {
 mod tests {
     use super::*;
     use std::fs;
+    use std::sync::Mutex;
     use tempfile::tempdir;
 
+    /// Guards tests that mutate process-wide env vars (e.g.
+    /// `SNIPPETS_APP_SECRET`) so the default parallel test runner can't
+    /// interleave one test's `set_var`/`remove_var` with another's.
+    static ENV_MUTEX: Mutex<()> = Mutex::new(());
+
+    fn lock_env() -> std::sync::MutexGuard<'static, ()> {
+        ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
     #[test]
     fn test_snippet_creation() {
         let s = Snippet {
             content: "hello".into(),
             created_at: OffsetDateTime::now_utc(),
+            owner: None,
         };
         assert_eq!(s.content, "hello");
     }
@@ -254,6 +1815,7 @@ mod tests {
         map.insert("test".into(), Snippet {
             content: "content".into(),
             created_at: OffsetDateTime::now_utc(),
+            owner: None,
         });
 
         storage.save(&map).unwrap();
@@ -277,6 +1839,7 @@ mod tests {
         map.insert("hello".into(), Snippet {
             content: "world".into(),
             created_at: OffsetDateTime::now_utc(),
+            owner: None,
         });
 
         storage.save(&map).unwrap();
@@ -288,6 +1851,235 @@ mod tests {
         assert_eq!(loaded["hello"].content, "world");
     }
 
+    #[test]
+    fn test_object_storage_crud() {
+        let dir = tempdir().unwrap();
+        let url = format!("file://{}", dir.path().to_str().unwrap());
+
+        let mut storage = ObjectStorage::new(&url).unwrap();
+        let sn = Snippet {
+            content: "world".into(),
+            created_at: OffsetDateTime::now_utc(),
+            owner: None,
+        };
+
+        storage.create("hello", &sn).unwrap();
+        assert_eq!(storage.get("hello").unwrap().unwrap().content, "world");
+        assert!(storage.get("missing").unwrap().is_none());
+
+        assert!(storage.delete("hello").unwrap());
+        assert!(storage.get("hello").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_token_issue_and_authorize() {
+        let _env_guard = lock_env();
+        env::set_var("SNIPPETS_APP_SECRET", "test-secret");
+
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("snippets.json");
+        let mut storage = JsonStorage::new(file_path.to_str().unwrap().to_string());
+
+        let token = issue_token(
+            &mut storage,
+            "alice".into(),
+            vec![Permission::Read, Permission::Write],
+            "secret-snippet".into(),
+            None,
+        )
+        .unwrap();
+
+        authorize(&mut storage, &token, "secret-snippet", Permission::Read).unwrap();
+        assert!(authorize(&mut storage, &token, "secret-snippet", Permission::Delete).is_err());
+        assert!(authorize(&mut storage, &token, "other-snippet", Permission::Read).is_err());
+
+        let records = storage.list_tokens().unwrap();
+        assert_eq!(records.len(), 1);
+
+        assert!(storage.revoke_token(&records[0].id).unwrap());
+        assert!(authorize(&mut storage, &token, "secret-snippet", Permission::Read).is_err());
+
+        env::remove_var("SNIPPETS_APP_SECRET");
+    }
+
+    #[test]
+    fn test_filter_listable_hides_owned_snippets_without_token() {
+        let _env_guard = lock_env();
+        env::set_var("SNIPPETS_APP_SECRET", "test-secret");
+
+        let mut storage = MemoryStorage::new();
+        storage
+            .create("public", &Snippet { content: "p".into(), created_at: OffsetDateTime::now_utc(), owner: None })
+            .unwrap();
+        storage
+            .create(
+                "secret",
+                &Snippet { content: "s".into(), created_at: OffsetDateTime::now_utc(), owner: Some("alice".into()) },
+            )
+            .unwrap();
+
+        let entries = storage.list(ListSnippetsQuery::default()).unwrap();
+
+        let visible = filter_listable(&mut storage, entries.clone(), None);
+        assert_eq!(visible.iter().map(|(n, _)| n.as_str()).collect::<Vec<_>>(), vec!["public"]);
+
+        let token = issue_token(&mut storage, "alice".into(), vec![Permission::Read], "secret".into(), None).unwrap();
+        let mut visible = filter_listable(&mut storage, entries, Some(&token));
+        visible.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(visible.iter().map(|(n, _)| n.as_str()).collect::<Vec<_>>(), vec!["public", "secret"]);
+
+        env::remove_var("SNIPPETS_APP_SECRET");
+    }
+
+    #[test]
+    fn test_memory_storage_crud() {
+        let mut storage = MemoryStorage::new();
+        let sn = Snippet {
+            content: "world".into(),
+            created_at: OffsetDateTime::now_utc(),
+            owner: None,
+        };
+
+        storage.create("hello", &sn).unwrap();
+        assert_eq!(storage.get("hello").unwrap().unwrap().content, "world");
+        assert!(storage.get("missing").unwrap().is_none());
+
+        assert!(storage.delete("hello").unwrap());
+        assert!(storage.get("hello").unwrap().is_none());
+        assert!(!storage.delete("hello").unwrap());
+    }
+
+    #[test]
+    fn test_settings_loads_config_file_with_env_override() {
+        let _env_guard = lock_env();
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("snippets.toml");
+        fs::write(&config_path, "log_level = \"debug\"\nstorage = \"MEMORY:\"\nregion = \"eu-west-1\"\n").unwrap();
+
+        env::set_var("SNIPPETS_APP_STORAGE", "JSON:/tmp/overridden.json");
+
+        settings::reset_for_test();
+        settings::init(Some(config_path.to_str().unwrap())).unwrap();
+
+        assert_eq!(settings::log_level(), "debug");
+        assert_eq!(settings::storage().unwrap(), "JSON:/tmp/overridden.json");
+        assert_eq!(settings::get("region"), Some("eu-west-1".to_string()));
+
+        env::remove_var("SNIPPETS_APP_STORAGE");
+        settings::reset_for_test();
+    }
+
+    #[test]
+    fn test_settings_get_falls_back_to_live_env_var() {
+        let _env_guard = lock_env();
+        env::remove_var("SNIPPETS_APP_WIDGET_COUNT");
+        assert_eq!(settings::get("widget_count"), None);
+
+        env::set_var("SNIPPETS_APP_WIDGET_COUNT", "7");
+        assert_eq!(settings::get("widget_count"), Some("7".to_string()));
+
+        env::remove_var("SNIPPETS_APP_WIDGET_COUNT");
+    }
+
+    #[test]
+    fn test_settings_get_env_overrides_config_file_extra() {
+        let _env_guard = lock_env();
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("snippets.toml");
+        fs::write(&config_path, "region = \"from-file\"\n").unwrap();
+
+        settings::reset_for_test();
+        settings::init(Some(config_path.to_str().unwrap())).unwrap();
+        assert_eq!(settings::get("region"), Some("from-file".to_string()));
+
+        env::set_var("SNIPPETS_APP_REGION", "from-env");
+        assert_eq!(settings::get("region"), Some("from-env".to_string()));
+
+        env::remove_var("SNIPPETS_APP_REGION");
+        settings::reset_for_test();
+    }
+
+    #[test]
+    fn test_sqlite_storage_crud() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("db.sqlite");
+        let path_str = file_path.to_str().unwrap().to_string();
+
+        let mut storage = SqliteStorage::new(path_str).unwrap();
+        let sn = Snippet {
+            content: "world".into(),
+            created_at: OffsetDateTime::now_utc(),
+            owner: None,
+        };
+
+        storage.create("hello", &sn).unwrap();
+        assert_eq!(storage.get("hello").unwrap().unwrap().content, "world");
+        assert!(storage.get("missing").unwrap().is_none());
+
+        assert!(storage.delete("hello").unwrap());
+        assert!(storage.get("hello").unwrap().is_none());
+        assert!(!storage.delete("hello").unwrap());
+    }
+
+    #[test]
+    fn test_sqlite_storage_list_treats_prefix_as_literal() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("db.sqlite");
+        let mut storage = SqliteStorage::new(file_path.to_str().unwrap().to_string()).unwrap();
+
+        let sn = |content: &str| Snippet {
+            content: content.into(),
+            created_at: OffsetDateTime::now_utc(),
+            owner: None,
+        };
+
+        storage.create("a_b", &sn("underscore")).unwrap();
+        storage.create("axb", &sn("wildcard-match")).unwrap();
+        storage.create("other", &sn("unrelated")).unwrap();
+
+        let results = storage
+            .list(ListSnippetsQuery {
+                name_prefix: Some("a_".into()),
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "a_b");
+    }
+
+    #[test]
+    fn test_json_storage_list_filters_and_paginates() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("snippets.json");
+        let path_str = file_path.to_str().unwrap().to_string();
+
+        let mut storage = JsonStorage::new(path_str);
+        let mut map = HashMap::new();
+        for name in ["alpha", "alphabet", "beta"] {
+            map.insert(
+                name.to_string(),
+                Snippet {
+                    content: name.to_string(),
+                    created_at: OffsetDateTime::now_utc(),
+                    owner: None,
+                },
+            );
+        }
+        storage.save(&map).unwrap();
+
+        let results = storage
+            .list(ListSnippetsQuery {
+                name_prefix: Some("alpha".into()),
+                limit: Some(1),
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "alpha");
+    }
+
     use assert_cmd::Command;
 
     #[test]